@@ -0,0 +1,157 @@
+//! Resilient HTTP layer for the PATH alerts endpoint: bounded timeouts,
+//! retry with exponential backoff + jitter on timeouts and 5xx responses,
+//! and conditional requests (`If-None-Match` / `If-Modified-Since`) so
+//! repeated polls skip parsing when nothing changed.
+
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::error::Error;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub(crate) const ALERTS_URL: &str =
+    "https://path-mppprod-app.azurewebsites.net/api/v1/AppContent/fetch?contentKey=PathAlert";
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct PathResponse {
+    #[serde(rename = "Content")]
+    pub(crate) content: String,
+}
+
+/// Tuning knobs for [`PathAlertsFetcher`]'s retry/backoff behavior.
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub base_backoff: Duration,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_retries: 3,
+            base_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Fetches the PATH alerts endpoint with retry/backoff on transient
+/// failures, and remembers the last response's validators (`ETag`,
+/// `Last-Modified`, and a hash of the parsed content) so subsequent calls
+/// send conditional headers and short-circuit parsing when nothing changed.
+pub struct PathAlertsFetcher {
+    client: Client,
+    config: FetchConfig,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    last_content_hash: Option<u64>,
+}
+
+impl PathAlertsFetcher {
+    pub fn new() -> Self {
+        Self::with_config(FetchConfig::default())
+    }
+
+    pub fn with_config(config: FetchConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::limited(5))
+            .build()
+            .expect("failed to build reqwest client");
+
+        Self {
+            client,
+            config,
+            etag: None,
+            last_modified: None,
+            last_content_hash: None,
+        }
+    }
+
+    /// Fetches the current alert page content, returning `Ok(None)` when the
+    /// server reports the content hasn't changed (a 304, or a byte-identical
+    /// body) so the caller can skip parsing entirely.
+    pub async fn fetch(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        let mut attempt = 0;
+
+        loop {
+            let mut request = self.client.get(ALERTS_URL);
+            if let Some(etag) = &self.etag {
+                request = request.header(IF_NONE_MATCH, etag.clone());
+            }
+            if let Some(last_modified) = &self.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified.clone());
+            }
+
+            match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status == StatusCode::NOT_MODIFIED {
+                        return Ok(None);
+                    }
+
+                    if status.is_server_error() && attempt < self.config.max_retries {
+                        attempt += 1;
+                        tokio::time::sleep(backoff_delay(self.config.base_backoff, attempt)).await;
+                        continue;
+                    }
+
+                    let response = response.error_for_status()?;
+                    if let Some(etag) = response.headers().get(ETAG) {
+                        self.etag = etag.to_str().ok().map(String::from);
+                    }
+                    if let Some(last_modified) = response.headers().get(LAST_MODIFIED) {
+                        self.last_modified = last_modified.to_str().ok().map(String::from);
+                    }
+
+                    let body = response.json::<PathResponse>().await?;
+                    let hash = content_hash(&body.content);
+                    if self.last_content_hash == Some(hash) {
+                        return Ok(None);
+                    }
+                    self.last_content_hash = Some(hash);
+                    return Ok(Some(body.content));
+                }
+                Err(err) if err.is_timeout() && attempt < self.config.max_retries => {
+                    attempt += 1;
+                    tokio::time::sleep(backoff_delay(self.config.base_backoff, attempt)).await;
+                }
+                Err(err) => return Err(Box::new(err)),
+            }
+        }
+    }
+}
+
+impl Default for PathAlertsFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn backoff_delay(base: Duration, attempt: u32) -> Duration {
+    // Cap the exponent well below the shift width so a caller-supplied
+    // `max_retries` can't trigger a shift overflow.
+    let exponent = attempt.saturating_sub(1).min(16);
+    base.saturating_mul(1 << exponent) + Duration::from_millis(jitter_ms())
+}
+
+/// Cheap jitter source derived from the current time, avoiding a dependency
+/// on a full RNG crate for what's just backoff smearing.
+fn jitter_ms() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    u64::from(nanos) % 250
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
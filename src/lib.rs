@@ -1,36 +1,35 @@
-use chrono::NaiveDateTime;
+use chrono::{Duration as ChronoDuration, LocalResult, NaiveDateTime, TimeZone};
+use chrono_tz::America::New_York;
 use gtfs_realtime::{
-    Alert, EntitySelector, FeedEntity, FeedHeader, FeedMessage, TimeRange,
     alert::{Cause, Effect},
     feed_header::Incrementality,
     translated_string::Translation,
+    Alert, EntitySelector, FeedEntity, FeedHeader, FeedMessage, TimeRange,
 };
-use gtfs_structures::Gtfs;
-use reqwest::Client;
+use gtfs_structures::{Gtfs, LocationType};
 use scraper::{Html, Selector};
-use serde::Deserialize;
+use std::collections::hash_map::{DefaultHasher, Entry};
+use std::collections::HashMap;
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Deserialize, Debug)]
-struct PathResponse {
-    #[serde(rename = "Content")]
-    content: String,
-}
-
-const ALERTS_URL: &str =
-    "https://path-mppprod-app.azurewebsites.net/api/v1/AppContent/fetch?contentKey=PathAlert";
+mod fetch;
+mod poller;
+pub use fetch::{FetchConfig, PathAlertsFetcher};
+pub use poller::AlertPoller;
 
+/// Fetches the current PATH alert page and parses it into a full-dataset
+/// feed. For repeated polling prefer [`AlertPoller`], which reuses a
+/// [`PathAlertsFetcher`] across calls to get retry/backoff and conditional
+/// caching.
 pub async fn fetch_path_alerts(gtfs: &Gtfs) -> Result<FeedMessage, Box<dyn Error>> {
-    let client = Client::new();
-
-    let resp = client
-        .get(ALERTS_URL)
-        .send()
+    let mut fetcher = PathAlertsFetcher::new();
+    let content = fetcher
+        .fetch()
         .await?
-        .json::<PathResponse>()
-        .await?;
-    parse_path_alerts(&resp.content, gtfs)
+        .ok_or("PATH alerts endpoint returned no content")?;
+    parse_path_alerts(&content, gtfs)
 }
 
 use regex::Regex;
@@ -40,11 +39,17 @@ static STATION_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("div.station").unwrap());
 static DATE_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("div.stationName table tr td strong span").unwrap());
+static STATION_NAME_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("div.stationName").unwrap());
+static STATION_NAME_TABLE_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("div.stationName table").unwrap());
 static TEXT_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("span.alertText").unwrap());
 static APOLOGIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"We (apologize|regret) (for )?(the|this|any)?( )?(inconvenience)( )?(this )?(may )?(have|has)?( )?(caused)?(.*\.?)").unwrap()
 });
+static EVERY_N_MINUTES_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"every \d+ minutes?").unwrap());
 
 pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<dyn Error>> {
     let clean_content = content.replace("&quot", "\"");
@@ -53,7 +58,7 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
     let mut entities = Vec::new();
     let current_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
 
-    for (index, element) in document.select(&STATION_SELECTOR).enumerate() {
+    for element in document.select(&STATION_SELECTOR) {
         let mut date_str = String::new();
         let mut time_str = String::new();
 
@@ -71,10 +76,7 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
         let parsed_time = NaiveDateTime::parse_from_str(&full_date_str, "%m/%d/%Y %I:%M %p");
 
         let timestamp = match parsed_time {
-            Ok(dt) => {
-                // Assuming Eastern Time (New York) - simplified
-                dt.and_utc().timestamp() as u64
-            }
+            Ok(naive) => eastern_naive_to_utc_timestamp(naive),
             Err(_) => current_timestamp, // Fallback
         };
 
@@ -94,12 +96,25 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
             .trim()
             .to_string();
 
+        // Extract the station name, which sits alongside (not inside) the
+        // nested date table within the same div.stationName block.
+        let station_name = element
+            .select(&STATION_NAME_SELECTOR)
+            .next()
+            .map(extract_station_name);
+        let stop_id = station_name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+            .and_then(|name| resolve_stop_id(name, gtfs));
+
         if clean_alert_text.is_empty() {
             continue;
         }
 
+        let route_ids = find_route_ids(&clean_alert_text, gtfs);
+
         let entity = FeedEntity {
-            id: format!("path_alert_{}", index),
+            id: alert_entity_id(&clean_alert_text, &route_ids),
             is_deleted: None,
             trip_update: None,
             vehicle: None,
@@ -109,7 +124,6 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
                     end: None,
                 }],
                 informed_entity: {
-                    let route_ids = find_route_ids(&clean_alert_text, gtfs);
                     let agency_id = gtfs
                         .agencies
                         .first()
@@ -119,6 +133,7 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
                     if route_ids.is_empty() {
                         vec![EntitySelector {
                             agency_id: agency_id.clone(),
+                            stop_id: stop_id.clone(),
                             ..Default::default()
                         }]
                     } else {
@@ -127,13 +142,14 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
                             .map(|route_id| EntitySelector {
                                 agency_id: agency_id.clone(),
                                 route_id: Some(route_id),
+                                stop_id: stop_id.clone(),
                                 ..Default::default()
                             })
                             .collect()
                     }
                 },
-                cause: Some(Cause::UnknownCause as i32),
-                effect: Some(Effect::UnknownEffect as i32),
+                cause: Some(classify_cause(&clean_alert_text) as i32),
+                effect: Some(classify_effect(&clean_alert_text) as i32),
                 url: None,
                 header_text: None,
                 description_text: Some(gtfs_realtime::TranslatedString {
@@ -158,10 +174,207 @@ pub fn parse_path_alerts(content: &str, gtfs: &Gtfs) -> Result<FeedMessage, Box<
             timestamp: Some(current_timestamp),
             feed_version: Some("1.0".to_string()),
         },
-        entity: entities,
+        entity: merge_duplicate_alerts(entities),
     })
 }
 
+/// Extracts the station name from a `div.stationName` block. Prefers the
+/// common case where the name sits as a bare text node alongside the nested
+/// date table; if PATH instead wraps the name in an element (so there's no
+/// direct text-node child), falls back to the block's full text with the
+/// date table's own text stripped out.
+fn extract_station_name(el: scraper::ElementRef) -> String {
+    let direct_text = el
+        .children()
+        .filter_map(|node| node.value().as_text())
+        .map(|text| text.as_ref())
+        .collect::<String>()
+        .trim()
+        .to_string();
+    if !direct_text.is_empty() {
+        return direct_text;
+    }
+
+    let table_text = el
+        .select(&STATION_NAME_TABLE_SELECTOR)
+        .next()
+        .map(|table| table.text().collect::<String>())
+        .unwrap_or_default();
+    el.text()
+        .collect::<String>()
+        .replace(&table_text, "")
+        .trim()
+        .to_string()
+}
+
+/// Collapses alerts that repeat across multiple stations (a system-wide
+/// notice shows up as one `div.station` per station) into a single entity
+/// per distinct alert text, compared case-insensitively. Selectors from every
+/// occurrence are unioned and the earliest start time among the group is
+/// kept, using a keyed buffer (lowercased text -> accumulated entity) so the
+/// merge stays O(n).
+fn merge_duplicate_alerts(entities: Vec<FeedEntity>) -> Vec<FeedEntity> {
+    let mut buffer: HashMap<String, FeedEntity> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+
+    for entity in entities {
+        let key = entity
+            .alert
+            .as_ref()
+            .and_then(|alert| alert.description_text.as_ref())
+            .and_then(|desc| desc.translation.first())
+            .map(|translation| translation.text.to_lowercase())
+            .unwrap_or_default();
+
+        match buffer.entry(key) {
+            Entry::Vacant(vacant) => {
+                order.push(vacant.key().clone());
+                vacant.insert(entity);
+            }
+            Entry::Occupied(mut occupied) => {
+                let existing = occupied.get_mut();
+                let Some(new_alert) = entity.alert else {
+                    continue;
+                };
+                let Some(existing_alert) = existing.alert.as_mut() else {
+                    continue;
+                };
+
+                for selector in new_alert.informed_entity {
+                    if !existing_alert.informed_entity.contains(&selector) {
+                        existing_alert.informed_entity.push(selector);
+                    }
+                }
+
+                let existing_start = existing_alert.active_period.first().and_then(|p| p.start);
+                let new_start = new_alert.active_period.first().and_then(|p| p.start);
+                if let (Some(existing_start), Some(new_start)) = (existing_start, new_start) {
+                    if new_start < existing_start {
+                        existing_alert.active_period[0].start = Some(new_start);
+                    }
+                }
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| buffer.remove(&key))
+        .collect()
+}
+
+/// Interprets a naive `America/New_York` timestamp parsed off the PATH alert
+/// page and converts it to a UTC epoch, resolving the two DST edge cases:
+/// a "spring forward" gap time is rounded forward to the next valid instant,
+/// and a "fall back" ambiguous time picks the earlier (pre-transition)
+/// occurrence deterministically.
+fn eastern_naive_to_utc_timestamp(naive: NaiveDateTime) -> u64 {
+    match New_York.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => dt.timestamp() as u64,
+        LocalResult::Ambiguous(earliest, _latest) => earliest.timestamp() as u64,
+        LocalResult::None => {
+            let mut candidate = naive;
+            loop {
+                candidate += ChronoDuration::minutes(1);
+                if let LocalResult::Single(dt) = New_York.from_local_datetime(&candidate) {
+                    break dt.timestamp() as u64;
+                }
+            }
+        }
+    }
+}
+
+/// Infers the GTFS-realtime `Effect` from the alert text by matching an
+/// ordered list of keyword/phrase groups (earliest match wins), falling back
+/// to `UnknownEffect` when nothing matches.
+fn classify_effect(text: &str) -> Effect {
+    let lower = text.to_lowercase();
+
+    if lower.contains("suspended") || lower.contains("no service") {
+        Effect::NoService
+    } else if lower.contains("delay") || lower.contains("running behind") {
+        Effect::SignificantDelays
+    } else if lower.contains("single-tracking")
+        || lower.contains("reduced")
+        || EVERY_N_MINUTES_REGEX.is_match(&lower)
+    {
+        Effect::ReducedService
+    } else if lower.contains("elevator") || lower.contains("escalator out of service") {
+        Effect::AccessibilityIssue
+    } else if lower.contains("detour") {
+        Effect::Detour
+    } else {
+        Effect::UnknownEffect
+    }
+}
+
+/// Infers the GTFS-realtime `Cause` from the alert text, mirroring
+/// [`classify_effect`]'s ordered keyword matching.
+fn classify_cause(text: &str) -> Cause {
+    let lower = text.to_lowercase();
+
+    if lower.contains("police activity") || lower.contains("investigation") {
+        Cause::PoliceActivity
+    } else if lower.contains("medical") {
+        Cause::MedicalEmergency
+    } else if lower.contains("signal")
+        || lower.contains("mechanical")
+        || lower.contains("equipment")
+    {
+        Cause::TechnicalProblem
+    } else if lower.contains("weather") || lower.contains("snow") || lower.contains("flooding") {
+        Cause::Weather
+    } else {
+        Cause::UnknownCause
+    }
+}
+
+/// Derives a stable id for an alert from its content rather than its position
+/// in the HTML, so the same real-world alert keeps the same id across polls
+/// even if PATH reorders the stations in the page.
+fn alert_entity_id(clean_text: &str, route_ids: &[String]) -> String {
+    let mut hasher = DefaultHasher::new();
+    clean_text.hash(&mut hasher);
+    route_ids.hash(&mut hasher);
+    format!("path_alert_{:016x}", hasher.finish())
+}
+
+/// Station name aliases for PATH naming quirks that don't line up with the
+/// GTFS `Stop.name` values, keyed by their normalized (lowercased, trimmed)
+/// form as scraped from the alert page.
+const STATION_NAME_ALIASES: &[(&str, &str)] = &[
+    ("wtc", "world trade center"),
+    ("33rd st", "33rd street"),
+    ("jsq", "journal square"),
+    ("hob", "hoboken"),
+    ("nwk", "newark"),
+];
+
+/// Lowercases, trims, and applies the PATH naming alias table so scraped
+/// station names and GTFS `Stop.name` values can be compared directly.
+fn normalize_station_name(name: &str) -> String {
+    let normalized = name.trim().to_lowercase();
+    STATION_NAME_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == normalized)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(normalized)
+}
+
+/// Resolves a scraped station name to a GTFS `stop_id` by matching against
+/// `gtfs.stops` on the normalized station name. Parent stations
+/// (`location_type` of [`LocationType::StopArea`]) are preferred over their
+/// platform children, which commonly share the same `stop_name`; remaining
+/// ties break on the lowest `stop_id` so the result is stable across runs.
+fn resolve_stop_id(station_name: &str, gtfs: &Gtfs) -> Option<String> {
+    let normalized = normalize_station_name(station_name);
+    gtfs.stops
+        .values()
+        .filter(|stop| stop.name.as_deref().is_some_and(|name| normalize_station_name(name) == normalized))
+        .min_by_key(|stop| (stop.location_type != LocationType::StopArea, stop.id.clone()))
+        .map(|stop| stop.id.clone())
+}
+
 fn find_route_ids(text: &str, gtfs: &Gtfs) -> Vec<String> {
     let route_map = [
         ("NWK-WTC", "Newark - World Trade Center"),
@@ -188,6 +401,8 @@ fn find_route_ids(text: &str, gtfs: &Gtfs) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fetch::PathResponse;
+    use gtfs_structures::Stop;
     use std::fs;
 
     #[test]
@@ -225,4 +440,160 @@ mod tests {
             }
         }
     }
+
+    fn html_station(station_name_html: &str) -> scraper::Html {
+        let content = format!(
+            r#"<div class="station">
+                <div class="stationName">
+                    {station_name_html}
+                    <table><tr><td><strong><span>01/02/2026 10:00 AM</span></strong></td></tr></table>
+                </div>
+                <span class="alertText">Test alert text.</span>
+            </div>"#
+        );
+        scraper::Html::parse_fragment(&content)
+    }
+
+    #[test]
+    fn extract_station_name_reads_bare_text_node() {
+        let document = html_station("World Trade Center");
+        let el = document.select(&STATION_NAME_SELECTOR).next().unwrap();
+        assert_eq!(extract_station_name(el), "World Trade Center");
+    }
+
+    #[test]
+    fn extract_station_name_falls_back_when_name_is_wrapped() {
+        let document = html_station("<span>World Trade Center</span>");
+        let el = document.select(&STATION_NAME_SELECTOR).next().unwrap();
+        assert_eq!(extract_station_name(el), "World Trade Center");
+    }
+
+    fn stop(id: &str, name: &str, location_type: LocationType) -> Stop {
+        Stop {
+            id: id.to_string(),
+            name: Some(name.to_string()),
+            location_type,
+            ..Default::default()
+        }
+    }
+
+    fn gtfs_with_stops(stops: HashMap<String, Stop>) -> Gtfs {
+        Gtfs {
+            routes: std::collections::HashMap::new(),
+            agencies: vec![],
+            stops,
+            trips: std::collections::HashMap::new(),
+            calendar: std::collections::HashMap::new(),
+            calendar_dates: std::collections::HashMap::new(),
+            fare_attributes: std::collections::HashMap::new(),
+            fare_rules: std::collections::HashMap::new(),
+            feed_info: vec![],
+            shapes: std::collections::HashMap::new(),
+            read_duration: std::time::Duration::new(0, 0),
+        }
+    }
+
+    #[test]
+    fn resolve_stop_id_applies_alias_table() {
+        let mut stops = HashMap::new();
+        stops.insert(
+            "104".to_string(),
+            stop("104", "World Trade Center", LocationType::StopArea),
+        );
+        let gtfs = gtfs_with_stops(stops);
+
+        assert_eq!(resolve_stop_id("WTC", &gtfs), Some("104".to_string()));
+    }
+
+    #[test]
+    fn resolve_stop_id_prefers_parent_station_over_platform() {
+        let mut stops = HashMap::new();
+        stops.insert(
+            "104".to_string(),
+            stop("104", "World Trade Center", LocationType::StopArea),
+        );
+        stops.insert(
+            "104A".to_string(),
+            stop("104A", "World Trade Center", LocationType::StopPoint),
+        );
+        let gtfs = gtfs_with_stops(stops);
+
+        // Regardless of HashMap iteration order, the parent station wins.
+        assert_eq!(resolve_stop_id("World Trade Center", &gtfs), Some("104".to_string()));
+    }
+
+    #[test]
+    fn classify_effect_picks_earliest_matching_group() {
+        let cases = [
+            ("Service suspended due to police activity", Effect::NoService),
+            ("Trains running behind schedule", Effect::SignificantDelays),
+            ("Single-tracking between JSQ-33", Effect::ReducedService),
+            ("Trains every 20 minutes", Effect::ReducedService),
+            ("Elevator out of service at WTC", Effect::AccessibilityIssue),
+            ("Buses on detour around construction", Effect::Detour),
+            ("Platform is wet from cleaning", Effect::UnknownEffect),
+            // "suspended" should win over "delay" since NoService is checked first.
+            ("Service suspended, expect delays", Effect::NoService),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(classify_effect(text), expected, "text: {text}");
+        }
+    }
+
+    #[test]
+    fn classify_cause_picks_earliest_matching_group() {
+        let cases = [
+            ("Delayed due to police activity investigation", Cause::PoliceActivity),
+            ("Delayed due to a medical emergency", Cause::MedicalEmergency),
+            ("Signal problem near Newark", Cause::TechnicalProblem),
+            ("Mechanical issue with equipment", Cause::TechnicalProblem),
+            ("Delays due to weather conditions", Cause::Weather),
+            ("Heavy snow in the area", Cause::Weather),
+            ("Station cleaning in progress", Cause::UnknownCause),
+            // "police activity" should win over "signal" since it's checked first.
+            ("Police activity near a signal", Cause::PoliceActivity),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(classify_cause(text), expected, "text: {text}");
+        }
+    }
+
+    #[test]
+    fn eastern_naive_to_utc_handles_normal_time() {
+        // 2026-01-15 12:00 EST (UTC-5), no DST in effect.
+        let naive = NaiveDateTime::parse_from_str("2026-01-15 12:00", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(eastern_naive_to_utc_timestamp(naive), 1768496400);
+    }
+
+    #[test]
+    fn eastern_naive_to_utc_rounds_spring_forward_gap_forward() {
+        // Clocks spring forward from 01:59:59 EST straight to 03:00:00 EDT on
+        // 2026-03-08, so 02:30 does not exist; it should round forward to the
+        // next valid instant (03:00 EDT).
+        let naive = NaiveDateTime::parse_from_str("2026-03-08 02:30", "%Y-%m-%d %H:%M").unwrap();
+        let expected =
+            NaiveDateTime::parse_from_str("2026-03-08 03:00", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(
+            eastern_naive_to_utc_timestamp(naive),
+            eastern_naive_to_utc_timestamp(expected)
+        );
+    }
+
+    #[test]
+    fn eastern_naive_to_utc_picks_earlier_occurrence_on_fall_back() {
+        // Clocks fall back from 01:59:59 EDT to 01:00:00 EST on 2026-11-01, so
+        // 01:30 occurs twice; the earlier (pre-transition, EDT) occurrence
+        // should be picked deterministically.
+        let naive = NaiveDateTime::parse_from_str("2026-11-01 01:30", "%Y-%m-%d %H:%M").unwrap();
+        let LocalResult::Ambiguous(earliest, _latest) = New_York.from_local_datetime(&naive)
+        else {
+            panic!("expected an ambiguous local time");
+        };
+        assert_eq!(
+            eastern_naive_to_utc_timestamp(naive),
+            earliest.timestamp() as u64
+        );
+    }
 }
@@ -0,0 +1,111 @@
+//! Long-running polling daemon that turns repeated one-shot fetches of the
+//! PATH alerts endpoint into a stream of differential `FeedMessage`s, so
+//! downstream consumers only see what changed between polls instead of
+//! re-parsing the full alert set every time.
+
+use crate::{parse_path_alerts, FetchConfig, PathAlertsFetcher};
+use gtfs_realtime::{feed_header::Incrementality, FeedEntity, FeedHeader, FeedMessage};
+use gtfs_structures::Gtfs;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Polls the PATH alerts endpoint on a fixed interval and streams
+/// [`Incrementality::Differential`] [`FeedMessage`]s: unchanged alerts are
+/// omitted, new or modified alerts are included in full, and alerts that
+/// disappeared since the last poll are emitted as a bare `FeedEntity` with
+/// `is_deleted: Some(true)` carrying only their stable id.
+pub struct AlertPoller {
+    gtfs: Gtfs,
+    interval: Duration,
+    fetcher: PathAlertsFetcher,
+}
+
+impl AlertPoller {
+    pub fn new(gtfs: Gtfs, interval: Duration) -> Self {
+        Self::with_fetch_config(gtfs, interval, FetchConfig::default())
+    }
+
+    pub fn with_fetch_config(gtfs: Gtfs, interval: Duration, fetch_config: FetchConfig) -> Self {
+        Self {
+            gtfs,
+            interval,
+            fetcher: PathAlertsFetcher::with_config(fetch_config),
+        }
+    }
+
+    /// Spawns the polling loop as a background task and returns the receiving
+    /// end of the channel it streams differential feed messages into.
+    pub fn spawn(mut self) -> mpsc::Receiver<FeedMessage> {
+        let (tx, rx) = mpsc::channel(8);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            let mut previous: HashMap<String, FeedEntity> = HashMap::new();
+
+            loop {
+                ticker.tick().await;
+
+                // The fetcher returns `None` when the server reports 304 or
+                // the content is byte-identical to the last poll, in which
+                // case there's nothing new to diff or emit.
+                match self.fetcher.fetch().await {
+                    Ok(Some(content)) => match parse_path_alerts(&content, &self.gtfs) {
+                        Ok(full) => {
+                            let diff = diff_feed(&previous, &full);
+                            previous = full
+                                .entity
+                                .into_iter()
+                                .map(|entity| (entity.id.clone(), entity))
+                                .collect();
+
+                            if tx.send(diff).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(err) => eprintln!("AlertPoller: failed to parse PATH alerts: {err}"),
+                    },
+                    Ok(None) => {}
+                    Err(err) => eprintln!("AlertPoller: failed to fetch PATH alerts: {err}"),
+                }
+            }
+        });
+        rx
+    }
+}
+
+/// Compares a freshly fetched full-dataset feed against the previously
+/// emitted entity set and produces the differential feed between them.
+fn diff_feed(previous: &HashMap<String, FeedEntity>, current: &FeedMessage) -> FeedMessage {
+    let mut entities = Vec::new();
+
+    for entity in &current.entity {
+        if previous.get(&entity.id) != Some(entity) {
+            entities.push(entity.clone());
+        }
+    }
+
+    for id in previous.keys() {
+        if !current.entity.iter().any(|entity| &entity.id == id) {
+            entities.push(FeedEntity {
+                id: id.clone(),
+                is_deleted: Some(true),
+                trip_update: None,
+                vehicle: None,
+                alert: None,
+                shape: None,
+                stop: None,
+                trip_modifications: None,
+            });
+        }
+    }
+
+    FeedMessage {
+        header: FeedHeader {
+            gtfs_realtime_version: "2.0".to_string(),
+            incrementality: Some(Incrementality::Differential as i32),
+            timestamp: current.header.timestamp,
+            feed_version: Some("1.0".to_string()),
+        },
+        entity: entities,
+    }
+}